@@ -0,0 +1,55 @@
+use crate::{Dictionary, StratifyBy};
+
+/// Build Japanese word/kanji frequency lists from a tokenized corpus.
+#[derive(clap::Parser, Debug)]
+#[command(name = "japanese-word-frequency-list")]
+pub struct Opt {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Tokenize the corpus and emit word, inflection, and (optionally)
+    /// kanji/stratified frequency lists.
+    Build(BuildOpt),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BuildOpt {
+    /// Glob pattern matching the input JSONL files.
+    #[arg(long, default_value = "Syosetu711K/syosetu711k-*.jsonl")]
+    pub input_glob: String,
+
+    /// Size of the rayon thread pool used to tokenize entries.
+    #[arg(long, default_value_t = 32)]
+    pub threads: usize,
+
+    /// MeCab dictionary the corpus is tagged against.
+    #[arg(long, value_enum, default_value = "ipadic")]
+    pub dictionary: Dictionary,
+
+    /// Output path for the word frequency list. Defaults to
+    /// frequency_list_<dictionary>.json.
+    #[arg(long)]
+    pub output: Option<String>,
+
+    /// Drop words seen fewer than this many times before serializing, to
+    /// prune the long tail of hapax legomena.
+    #[arg(long, default_value_t = 1)]
+    pub min_frequency: u32,
+
+    /// Optional JMdict XML dump to attach glosses from.
+    #[arg(long)]
+    pub jmdict: Option<String>,
+
+    /// Optional KANJIDIC2 XML dump to annotate kanji with. When set, also
+    /// emits frequency_list_kanji.json.
+    #[arg(long)]
+    pub kanjidic2: Option<String>,
+
+    /// Meta field to additionally stratify the word frequency list by,
+    /// emitting frequency_list_stratified.json.
+    #[arg(long, value_enum)]
+    pub stratify_by: Option<StratifyBy>,
+}
@@ -0,0 +1,39 @@
+use std::sync::OnceLock;
+
+/// A single deinflection rule: if the surface texts immediately following a
+/// 動詞 node match `suffix_sequence` token-for-token, they're folded back
+/// into the verb as one inflected word named `name`. `priority` only breaks
+/// ties between rules whose `suffix_sequence` is the same length.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub suffix_sequence: Vec<String>,
+    pub priority: u8,
+}
+
+#[derive(serde::Deserialize)]
+struct RuleFile {
+    rule: Vec<Rule>,
+}
+
+/// The embedded rule table, sorted so that the longest `suffix_sequence`
+/// is tried first — this is what makes a 4-token match like ませんでした
+/// win over the 2-token ません it starts with. Contributors add new
+/// conjugation patterns by editing `assets/deinflection_rules.toml`, not
+/// this file.
+pub fn rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let raw = include_str!("../assets/deinflection_rules.toml");
+        let mut rules: Vec<Rule> = toml::from_str::<RuleFile>(raw)
+            .expect("embedded deinflection rules are valid TOML")
+            .rule;
+        rules.sort_by(|a, b| {
+            b.suffix_sequence
+                .len()
+                .cmp(&a.suffix_sequence.len())
+                .then(b.priority.cmp(&a.priority))
+        });
+        rules
+    })
+}
@@ -0,0 +1,163 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rustc_hash::FxHashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::OnceLock;
+
+#[derive(serde::Deserialize)]
+struct JMdictEntityDef {
+    name: String,
+    expansion: String,
+}
+
+#[derive(serde::Deserialize)]
+struct EntityFile {
+    entity: Vec<JMdictEntityDef>,
+}
+
+/// JMdict.xml declares its `<pos>`/`<field>`/`<misc>`/`<dial>` values as
+/// custom DTD entities in the file's internal subset (e.g. `<pos>&v5u;</pos>`)
+/// rather than as plain text, so quick-xml's bare `unescape()` — which only
+/// knows the five predefined XML entities plus numeric refs — can't resolve
+/// them. This table mirrors the DTD's entity declarations so `unescape_with`
+/// can. Contributors add new rows to `assets/jmdict_entities.toml` as
+/// upstream JMdict adds new tags, rather than editing this file.
+fn entities() -> &'static FxHashMap<String, String> {
+    static ENTITIES: OnceLock<FxHashMap<String, String>> = OnceLock::new();
+    ENTITIES.get_or_init(|| {
+        let raw = include_str!("../assets/jmdict_entities.toml");
+        toml::from_str::<EntityFile>(raw)
+            .expect("embedded JMdict entity table is valid TOML")
+            .entity
+            .into_iter()
+            .map(|e| (e.name, e.expansion))
+            .collect()
+    })
+}
+
+/// A single JMdict `<entry>`: the kanji (`keb`) and reading (`reb`)
+/// headwords it can be written as, kept separate so a lookup can tell which
+/// reading belongs to this entry, plus the part-of-speech tags and glosses
+/// attached to its senses. We flatten senses into a single gloss/pos list
+/// rather than modelling them individually, since the frequency list only
+/// needs something to show next to a word, not a full dictionary entry.
+#[derive(Debug, Clone)]
+pub struct JMdictEntry {
+    pub kanji: Vec<String>,
+    pub readings: Vec<String>,
+    pub pos: Vec<String>,
+    pub glosses: Vec<String>,
+}
+
+/// Parses a JMdict XML dump and indexes it by every kanji (`keb`) and
+/// reading (`reb`) headword, mirroring how datagengo's `index_jmdict` keys
+/// its `HashMap<&str, Vec<Node>>`. A single surface form can map to several
+/// entries (homographs), so lookups return a `Vec`.
+pub fn index_jmdict(path: &str) -> anyhow::Result<FxHashMap<String, Vec<JMdictEntry>>> {
+    let file = File::open(path)?;
+    let mut reader = Reader::from_reader(BufReader::new(file));
+    reader.config_mut().trim_text(true);
+
+    let mut index: FxHashMap<String, Vec<JMdictEntry>> = FxHashMap::default();
+    let mut buf = Vec::new();
+
+    let mut kanji: Vec<String> = Vec::new();
+    let mut readings: Vec<String> = Vec::new();
+    let mut pos: Vec<String> = Vec::new();
+    let mut glosses: Vec<String> = Vec::new();
+    let mut in_entry = false;
+    let mut current_tag: Vec<u8> = Vec::new();
+    // <gloss xml:lang="fre">...</gloss> etc. appear alongside the English
+    // ones in the standard multilingual JMdict.xml dump; xml:lang defaults
+    // to "eng" when absent, mirroring kanjidic.rs's current_m_lang_is_en.
+    let mut current_gloss_is_en = true;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(e) => {
+                current_tag = e.name().as_ref().to_vec();
+                if current_tag == b"entry" {
+                    in_entry = true;
+                    kanji.clear();
+                    readings.clear();
+                    pos.clear();
+                    glosses.clear();
+                }
+                if current_tag == b"gloss" {
+                    current_gloss_is_en = match e.try_get_attribute("xml:lang")? {
+                        Some(attr) => attr.value.as_ref() == b"eng",
+                        None => true,
+                    };
+                }
+            }
+            Event::Text(e) if in_entry => {
+                let text = e.unescape_with(|entity| entities().get(entity).map(String::as_str))?;
+                let text = text.into_owned();
+                match current_tag.as_slice() {
+                    b"keb" => kanji.push(text),
+                    b"reb" => readings.push(text),
+                    b"pos" => pos.push(text),
+                    b"gloss" if current_gloss_is_en => glosses.push(text),
+                    _ => {}
+                }
+            }
+            Event::End(e) if e.name().as_ref() == b"entry" => {
+                in_entry = false;
+                let entry = JMdictEntry {
+                    kanji: kanji.clone(),
+                    readings: readings.clone(),
+                    pos: pos.clone(),
+                    glosses: glosses.clone(),
+                };
+                for headword in kanji.iter().chain(readings.iter()) {
+                    index
+                        .entry(headword.clone())
+                        .or_default()
+                        .push(entry.clone());
+                }
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(index)
+}
+
+/// Looks up a word by its dictionary form first, falling back to its
+/// reading so that entries written only in kana (or whose kanji form didn't
+/// survive tokenization) still resolve. A kanji headword is often shared by
+/// several homographs with different readings, so among the candidates
+/// found this way we prefer the one whose own `readings` actually contains
+/// `reading`, and only fall back to the first candidate when none match
+/// (e.g. the reading couldn't be recovered from the feature string).
+pub fn lookup_entry<'a>(
+    index: &'a FxHashMap<String, Vec<JMdictEntry>>,
+    dictionary_form: &str,
+    reading: &str,
+) -> Option<&'a JMdictEntry> {
+    // MeCab gives readings in katakana, while JMdict writes the `reb` of
+    // native words in hiragana, so the reading is also tried in hiragana.
+    let hiragana: String = reading
+        .chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    let candidates = index
+        .get(dictionary_form)
+        .or_else(|| index.get(reading))
+        .or_else(|| index.get(hiragana.as_str()))?;
+    if !reading.is_empty() {
+        if let Some(entry) = candidates
+            .iter()
+            .find(|entry| entry.readings.iter().any(|r| r == reading || *r == hiragana))
+        {
+            return Some(entry);
+        }
+    }
+    candidates.first()
+}
@@ -0,0 +1,83 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rustc_hash::FxHashMap;
+use std::fs::File;
+use std::io::BufReader;
+
+/// Per-character attributes pulled from a single KANJIDIC2 `<character>`
+/// entry: the school grade it's taught in, its JLPT level (pre-2010
+/// KANJIDIC2 releases only), stroke count, and its readings/meanings.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct KanjiInfo {
+    pub grade: Option<u8>,
+    pub jlpt: Option<u8>,
+    pub strokes: Option<u8>,
+    pub on: Vec<String>,
+    pub kun: Vec<String>,
+    pub meanings: Vec<String>,
+}
+
+/// Parses a KANJIDIC2 XML dump into a map keyed by the kanji itself.
+pub fn index_kanjidic2(path: &str) -> anyhow::Result<FxHashMap<char, KanjiInfo>> {
+    let file = File::open(path)?;
+    let mut reader = Reader::from_reader(BufReader::new(file));
+    reader.config_mut().trim_text(true);
+
+    let mut index = FxHashMap::default();
+    let mut buf = Vec::new();
+
+    let mut literal: Option<char> = None;
+    let mut info = KanjiInfo::default();
+    let mut in_character = false;
+    let mut current_tag: Vec<u8> = Vec::new();
+    let mut current_r_type: Option<String> = None;
+    let mut current_m_lang_is_en = true;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(e) | Event::Empty(e) => {
+                current_tag = e.name().as_ref().to_vec();
+                if current_tag == b"character" {
+                    in_character = true;
+                    literal = None;
+                    info = KanjiInfo::default();
+                }
+                if current_tag == b"reading" {
+                    current_r_type = e
+                        .try_get_attribute("r_type")?
+                        .map(|a| String::from_utf8_lossy(&a.value).into_owned());
+                }
+                if current_tag == b"meaning" {
+                    current_m_lang_is_en = e.try_get_attribute("m_lang")?.is_none();
+                }
+            }
+            Event::Text(e) if in_character => {
+                let text = e.unescape()?.into_owned();
+                match current_tag.as_slice() {
+                    b"literal" => literal = text.chars().next(),
+                    b"grade" => info.grade = text.parse().ok(),
+                    b"jlpt" => info.jlpt = text.parse().ok(),
+                    b"stroke_count" if info.strokes.is_none() => info.strokes = text.parse().ok(),
+                    b"reading" => match current_r_type.as_deref() {
+                        Some("ja_on") => info.on.push(text),
+                        Some("ja_kun") => info.kun.push(text),
+                        _ => {}
+                    },
+                    b"meaning" if current_m_lang_is_en => info.meanings.push(text),
+                    _ => {}
+                }
+            }
+            Event::End(e) if e.name().as_ref() == b"character" => {
+                in_character = false;
+                if let Some(literal) = literal {
+                    index.insert(literal, info.clone());
+                }
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(index)
+}
@@ -1,3 +1,4 @@
+use clap::Parser;
 use mecab::Tagger;
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
@@ -6,6 +7,13 @@ use std::{cell::RefCell, fs::File, io::BufReader};
 
 use mimalloc::MiMalloc;
 
+mod cli;
+mod deinflect;
+mod jmdict;
+mod kanjidic;
+
+use cli::Opt;
+
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
@@ -32,10 +40,41 @@ struct Meta {
 #[derive(serde::Deserialize, serde::Serialize)]
 struct Entry {
     text: String,
-    #[serde(skip)]
+    #[serde(flatten)]
     meta: Meta,
 }
 
+/// Which `Meta` field to stratify the frequency list by. `biggenre` is used
+/// as-is, one bucket per value; `isr18` and `points` are folded into coarse,
+/// fixed buckets rather than exposing the raw field value, since e.g.
+/// `points` is a long-tailed distribution and per-value buckets would mostly
+/// be singletons.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum StratifyBy {
+    #[value(name = "biggenre")]
+    BigGenre,
+    #[value(name = "isr18")]
+    IsR18,
+    Points,
+}
+
+impl StratifyBy {
+    fn bucket(&self, meta: &Meta) -> String {
+        match self {
+            StratifyBy::BigGenre => meta.biggenre.to_string(),
+            StratifyBy::IsR18 => match meta.isr18 {
+                Some(v) if v != 0 => "r18".to_string(),
+                _ => "general".to_string(),
+            },
+            StratifyBy::Points => match meta.points {
+                0..=99 => "low".to_string(),
+                100..=999 => "mid".to_string(),
+                _ => "high".to_string(),
+            },
+        }
+    }
+}
+
 fn is_separator(c: char) -> bool {
     c.is_whitespace()
         || c.is_ascii_punctuation()
@@ -52,16 +91,83 @@ struct Node {
     text: String,
     pos: String,
     dictionary_form: String,
+    reading: String,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 struct NodeFrequency {
     pos: String,
     dictionary_form: String,
+    reading: String,
     frequency: u32,
+    #[serde(default)]
+    glosses: Vec<String>,
+    // Part-of-speech tags from the JMdict entry, expanded through the entity
+    // table (e.g. "Godan verb with 'u' ending"), distinct from `pos` above
+    // which is MeCab's tag (e.g. "動詞").
+    #[serde(default)]
+    jmdict_pos: Vec<String>,
+}
+
+// IPADIC only fills in the reading for known words; unknown tokens leave the
+// feature vector short and we fall back to the surface form when it is pure
+// katakana (it already *is* the reading in that case).
+fn is_katakana(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| matches!(c, '\u{30A0}'..='\u{30FF}'))
+}
+
+// CJK Unified Ideographs, the block covering the overwhelming majority of
+// kanji in modern text. A direct range check instead of a compiled \p{Han}
+// regex, since this runs once per character over the raw, untokenized
+// corpus text rather than once per token.
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}')
+}
+
+/// The MeCab dictionary a corpus is tagged against. IPADIC and UniDic lay
+/// their comma-separated feature columns out differently, so the column
+/// offsets for the base (dictionary) form and the kana reading have to travel
+/// with the dictionary choice instead of being hard-coded in `parse_text`.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Dictionary {
+    Ipadic,
+    #[value(name = "unidic")]
+    UniDic,
 }
 
-fn parse_text(tagger: &mut Tagger, text: &str) -> Vec<Node> {
+impl Dictionary {
+    fn mecab_arg(&self) -> &'static str {
+        match self {
+            Dictionary::Ipadic => "",
+            Dictionary::UniDic => "-d /var/lib/mecab/dic/unidic",
+        }
+    }
+
+    // Index (0-based, from the start of the feature string) of the base form.
+    // IPADIC: 品詞,品詞細分類1,品詞細分類2,品詞細分類3,活用型,活用形,原形,読み,発音
+    // (原形 at 6). UniDic: pos1,pos2,pos3,pos4,cType,cForm,lForm,lemma,orth,
+    // pron,... (lemma at 7).
+    fn base_form_index(&self) -> usize {
+        match self {
+            Dictionary::Ipadic => 6,
+            Dictionary::UniDic => 7,
+        }
+    }
+
+    // Index (0-based, from the start of the feature string) of the reading.
+    // IPADIC's 読み sits right after 原形, at 7. UniDic has no direct
+    // equivalent; lForm (6), the lexical/dictionary reading, is the closest
+    // analogue — pron (9) is the sound-changed pronunciation, not the
+    // dictionary reading.
+    fn reading_index(&self) -> usize {
+        match self {
+            Dictionary::Ipadic => 7,
+            Dictionary::UniDic => 6,
+        }
+    }
+}
+
+fn parse_text(tagger: &mut Tagger, text: &str, dictionary: Dictionary) -> Vec<Node> {
     let result = tagger.parse_to_node(text);
 
     result
@@ -73,297 +179,99 @@ fn parse_text(tagger: &mut Tagger, text: &str) -> Vec<Node> {
             )
         })
         .map(|node| {
-            if true {
-                let text = node.surface[..node.length as usize].to_string();
-                let mut iter = node.feature.split(',');
-                let pos = iter.next().unwrap().to_string();
-                let mut iter = iter.skip(5);
-                let dictionary_form = iter.next().unwrap_or("").to_string();
-
-                Node {
-                    text,
-                    pos,
-                    dictionary_form,
-                }
+            let text = node.surface[..node.length as usize].to_string();
+            let mut fields = node.feature.split(',');
+            let pos = fields.next().unwrap().to_string();
+
+            let dictionary_form = fields
+                .clone()
+                .nth(dictionary.base_form_index() - 1)
+                .unwrap_or("")
+                .to_string();
+
+            let reading = fields
+                .nth(dictionary.reading_index() - 1)
+                .unwrap_or("")
+                .to_string();
+            let reading = if reading.is_empty() && is_katakana(&text) {
+                text.clone()
             } else {
-                let text = node.surface[..node.length as usize].to_string();
-                let mut iter = node.feature.split(',');
-                let pos = iter.next().unwrap().to_string();
-                let mut iter = iter.skip(8);
-                let reading = iter.next().unwrap_or("").to_string();
-                let dictionary_form = iter.next().unwrap_or("").to_string();
-
-                Node {
-                    text,
-                    pos,
-                    dictionary_form,
-                }
+                reading
+            };
+
+            Node {
+                text,
+                pos,
+                dictionary_form,
+                reading,
             }
         })
         .collect::<Vec<_>>()
 }
 
-fn process_nodes(nodes: Vec<Node>) -> (Vec<Node>, FxHashMap<&'static str, u32>) {
+// Greedily folds 助動詞/助詞 chains following a 動詞 node back into it using
+// the rule table in `deinflect::rules()`, which is sorted longest-sequence
+// first so e.g. ませんでした is matched whole rather than as ません plus a
+// dangling でした.
+fn process_nodes(nodes: Vec<Node>) -> (Vec<Node>, FxHashMap<String, u32>) {
     let mut result = Vec::new();
-    let mut inflections_frequency = FxHashMap::default();
-    let mut insert_inflection = |inflection: &'static str| {
-        inflections_frequency
-            .entry(inflection)
-            .and_modify(|v| *v += 1)
-            .or_insert(1u32);
-    };
+    let mut inflections_frequency: FxHashMap<String, u32> = FxHashMap::default();
 
-    let mut i = 0;
-    let peek_n = |i: usize, n: usize| {
-        if (i + n) < nodes.len() {
-            Some(&nodes[i + n])
-        } else {
-            None
+    let matching_rule = |i: usize| {
+        if nodes[i].pos != "動詞" {
+            return None;
         }
+        deinflect::rules().iter().find(|rule| {
+            rule.suffix_sequence
+                .iter()
+                .enumerate()
+                .all(|(offset, suffix)| {
+                    nodes
+                        .get(i + 1 + offset)
+                        .is_some_and(|node| node.text == *suffix)
+                })
+        })
     };
+
+    let mut i = 0;
     while i < nodes.len() {
         let node = &nodes[i];
 
-        let a = peek_n(i, 1).map(|v| v.text.as_ref());
-        let b = peek_n(i, 2).map(|v| v.text.as_ref());
-        let c = peek_n(i, 3).map(|v| v.text.as_ref());
-        let d = peek_n(i, 4).map(|v| v.text.as_ref());
-
-        if let "動詞" = node.pos.as_ref() {
-            match (a, b, c, d) {
-                (Some("ませ"), Some("ん"), Some("でし"), Some("た")) => {
-                    insert_inflection("ませんでした");
-                    result.push(Node {
-                        text: format!("{}ませんでした", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 4;
-                }
-                (Some("させ"), Some("られ"), Some("ない"), _) => {
-                    insert_inflection("させられない");
-                    result.push(Node {
-                        text: format!("{}させられない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 3;
-                }
-                (Some("られ"), Some("ませ"), Some("ん"), _) => {
-                    insert_inflection("られません");
-                    result.push(Node {
-                        text: format!("{}られません", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 3;
-                }
-                (Some("させ"), Some("ない"), _, _) => {
-                    insert_inflection("させない");
-                    result.push(Node {
-                        text: format!("{}させない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("させ"), Some("られる"), _, _) => {
-                    insert_inflection("させられる");
-                    result.push(Node {
-                        text: format!("{}させられる", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("なかっ"), Some("た"), _, _) => {
-                    insert_inflection("なかった");
-                    result.push(Node {
-                        text: format!("{}なかった", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("なく"), Some("て"), _, _) => {
-                    insert_inflection("なくて");
-                    result.push(Node {
-                        text: format!("{}なくて", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("まし"), Some("た"), _, _) => {
-                    insert_inflection("ました");
-                    result.push(Node {
-                        text: format!("{}ました", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("せ"), Some("ない"), _, _) => {
-                    insert_inflection("せない");
-                    result.push(Node {
-                        text: format!("{}せない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("ませ"), Some("ん"), _, _) => {
-                    insert_inflection("ません");
-                    result.push(Node {
-                        text: format!("{}ません", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("られ"), Some("ない"), _, _) => {
-                    insert_inflection("られない");
-                    result.push(Node {
-                        text: format!("{}られない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("られ"), Some("ます"), _, _) => {
-                    insert_inflection("られます");
-                    result.push(Node {
-                        text: format!("{}られます", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("れ"), Some("ない"), _, _) => {
-                    insert_inflection("れない");
-                    result.push(Node {
-                        text: format!("{}れない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 2;
-                }
-                (Some("させる"), _, _, _) => {
-                    insert_inflection("させる");
-                    result.push(Node {
-                        text: format!("{}させる", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("せる"), _, _, _) => {
-                    insert_inflection("せる");
-                    result.push(Node {
-                        text: format!("{}せる", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("た"), _, _, _) => {
-                    insert_inflection("た");
-                    result.push(Node {
-                        text: format!("{}た", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("だ"), _, _, _) => {
-                    insert_inflection("だ");
-                    result.push(Node {
-                        text: format!("{}だ", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("て"), _, _, _) => {
-                    insert_inflection("て");
-                    result.push(Node {
-                        text: format!("{}て", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("で"), _, _, _) => {
-                    insert_inflection("で");
-                    result.push(Node {
-                        text: format!("{}で", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("な"), _, _, _) => {
-                    insert_inflection("な");
-                    result.push(Node {
-                        text: format!("{}な", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("ない"), _, _, _) => {
-                    insert_inflection("ない");
-                    result.push(Node {
-                        text: format!("{}ない", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("ます"), _, _, _) => {
-                    insert_inflection("ます");
-                    result.push(Node {
-                        text: format!("{}ます", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("られる"), _, _, _) => {
-                    insert_inflection("られる");
-                    result.push(Node {
-                        text: format!("{}られる", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                (Some("れる"), _, _, _) => {
-                    insert_inflection("れる");
-                    result.push(Node {
-                        text: format!("{}れる", node.text),
-                        pos: node.pos.clone(),
-                        dictionary_form: node.dictionary_form.clone(),
-                    });
-                    i += 1;
-                }
-                _ => {
-                    result.push(node.clone());
-                }
+        match matching_rule(i) {
+            Some(rule) => {
+                inflections_frequency
+                    .entry(rule.name.clone())
+                    .and_modify(|v| *v += 1)
+                    .or_insert(1);
+                result.push(Node {
+                    text: format!("{}{}", node.text, rule.suffix_sequence.concat()),
+                    pos: node.pos.clone(),
+                    dictionary_form: node.dictionary_form.clone(),
+                    reading: node.reading.clone(),
+                });
+                i += 1 + rule.suffix_sequence.len();
+            }
+            None => {
+                result.push(node.clone());
+                i += 1;
             }
-        } else {
-            result.push(node.clone());
         }
-
-        i += 1;
     }
 
     (result, inflections_frequency)
 }
 
-fn process_with_ipadic() -> anyhow::Result<()> {
+fn process(opt: &cli::BuildOpt) -> anyhow::Result<()> {
+    let dictionary = opt.dictionary;
+    let stratify_by = opt.stratify_by;
+
+    // A thread_local! initializer cannot capture `dictionary`, so the MeCab
+    // argument is handed to it through a static set before any tagging.
+    static MECAB_ARG: std::sync::OnceLock<&'static str> = std::sync::OnceLock::new();
+    MECAB_ARG.get_or_init(|| dictionary.mecab_arg());
     thread_local! {
-        static TAGGER: RefCell<Tagger> = RefCell::new(Tagger::new(""));
+        static TAGGER: RefCell<Tagger> =
+            RefCell::new(Tagger::new(*MECAB_ARG.get().expect("MECAB_ARG is set before tagging")));
     }
     let regex = regex::Regex::new(r"^(\p{Han}|\p{Katakana}|\p{Hiragana})+$")?;
     let spinner_style =
@@ -373,11 +281,16 @@ fn process_with_ipadic() -> anyhow::Result<()> {
     let pb = indicatif::ProgressBar::new(1);
     pb.set_style(spinner_style);
 
+    let input_paths = glob::glob(&opt.input_glob)?.collect::<Result<Vec<_>, _>>()?;
+
     let mut global_frequency_list = FxHashMap::default();
     let mut global_inflections_frequency = FxHashMap::default();
-    for i in 0..=20 {
-        pb.set_prefix(format!("[{i:02}/20]"));
-        let file = File::open(format!("Syosetu711K/syosetu711k-{i:02}.jsonl"))?;
+    let mut global_kanji_frequency: FxHashMap<char, u32> = FxHashMap::default();
+    let mut global_stratified: FxHashMap<String, FxHashMap<String, NodeFrequency>> =
+        FxHashMap::default();
+    for (i, path) in input_paths.iter().enumerate() {
+        pb.set_prefix(format!("[{:02}/{:02}]", i + 1, input_paths.len()));
+        let file = File::open(path)?;
         let reader = BufReader::new(file);
 
         let entries = reader
@@ -395,21 +308,50 @@ fn process_with_ipadic() -> anyhow::Result<()> {
                 TAGGER.with(|tagger| {
                     let mut frequency_list = FxHashMap::default();
                     let mut inflections_frequency = FxHashMap::default();
+                    let mut kanji_frequency: FxHashMap<char, u32> = FxHashMap::default();
+                    let mut stratified: FxHashMap<String, FxHashMap<String, NodeFrequency>> =
+                        FxHashMap::default();
+                    let bucket = stratify_by.map(|stratify_by| stratify_by.bucket(&entry.meta));
+
+                    for c in entry.text.chars() {
+                        if is_han(c) {
+                            kanji_frequency.entry(c).and_modify(|v| *v += 1).or_insert(1);
+                        }
+                    }
 
                     for part in entry.text.split(is_separator) {
-                        let nodes = parse_text(&mut tagger.borrow_mut(), part);
+                        let nodes = parse_text(&mut tagger.borrow_mut(), part, dictionary);
                         let processed_nodes = process_nodes(nodes);
 
                         for node in processed_nodes.0 {
                             if regex.captures(&node.text).is_some() {
                                 frequency_list
-                                    .entry(node.text)
+                                    .entry(node.text.clone())
                                     .and_modify(|node: &mut NodeFrequency| node.frequency += 1)
                                     .or_insert_with(|| NodeFrequency {
-                                        pos: node.pos,
-                                        dictionary_form: node.dictionary_form,
+                                        pos: node.pos.clone(),
+                                        dictionary_form: node.dictionary_form.clone(),
+                                        reading: node.reading.clone(),
                                         frequency: 1,
+                                        glosses: Vec::new(),
+                                        jmdict_pos: Vec::new(),
                                     });
+
+                                if let Some(bucket) = &bucket {
+                                    stratified
+                                        .entry(bucket.clone())
+                                        .or_default()
+                                        .entry(node.text)
+                                        .and_modify(|node: &mut NodeFrequency| node.frequency += 1)
+                                        .or_insert_with(|| NodeFrequency {
+                                            pos: node.pos,
+                                            dictionary_form: node.dictionary_form,
+                                            reading: node.reading,
+                                            frequency: 1,
+                                            glosses: Vec::new(),
+                                            jmdict_pos: Vec::new(),
+                                        });
+                                }
                             }
                         }
 
@@ -421,7 +363,7 @@ fn process_with_ipadic() -> anyhow::Result<()> {
                         }
                     }
 
-                    (frequency_list, inflections_frequency)
+                    (frequency_list, inflections_frequency, kanji_frequency, stratified)
                 })
             })
             .collect::<Vec<_>>();
@@ -440,10 +382,80 @@ fn process_with_ipadic() -> anyhow::Result<()> {
                     .and_modify(|v| *v += frequency)
                     .or_insert(frequency);
             }
+
+            for (kanji, frequency) in frequency_list.2 {
+                global_kanji_frequency
+                    .entry(kanji)
+                    .and_modify(|v| *v += frequency)
+                    .or_insert(frequency);
+            }
+
+            for (bucket, words) in frequency_list.3 {
+                let global_bucket = global_stratified.entry(bucket).or_default();
+                for (key, value) in words {
+                    global_bucket
+                        .entry(key)
+                        .and_modify(|node: &mut NodeFrequency| node.frequency += value.frequency)
+                        .or_insert_with(|| value);
+                }
+            }
         }
     }
 
-    let output = File::create("frequency_list_ipadic.json")?;
+    global_frequency_list.retain(|_, node: &mut NodeFrequency| node.frequency >= opt.min_frequency);
+    for words in global_stratified.values_mut() {
+        words.retain(|_, node: &mut NodeFrequency| node.frequency >= opt.min_frequency);
+    }
+
+    if let Some(kanjidic2_path) = &opt.kanjidic2 {
+        let index = kanjidic::index_kanjidic2(kanjidic2_path)?;
+        let kanji_list: Vec<_> = global_kanji_frequency
+            .iter()
+            .map(|(&character, &frequency)| {
+                let info = index.get(&character).cloned().unwrap_or_default();
+                serde_json::json!({
+                    "char": character.to_string(),
+                    "frequency": frequency,
+                    "grade": info.grade,
+                    "jlpt": info.jlpt,
+                    "strokes": info.strokes,
+                    "on": info.on,
+                    "kun": info.kun,
+                    "meanings": info.meanings,
+                })
+            })
+            .collect();
+
+        let output = File::create("frequency_list_kanji.json")?;
+        serde_json::to_writer(std::io::BufWriter::new(output), &kanji_list)?;
+    }
+
+    if let Some(jmdict_path) = &opt.jmdict {
+        let index = jmdict::index_jmdict(jmdict_path)?;
+        for node in global_frequency_list.values_mut() {
+            if let Some(entry) = jmdict::lookup_entry(&index, &node.dictionary_form, &node.reading)
+            {
+                node.glosses = entry.glosses.clone();
+                node.jmdict_pos = entry.pos.clone();
+            }
+        }
+    }
+
+    if stratify_by.is_some() {
+        let buckets: FxHashMap<_, _> = global_stratified
+            .into_iter()
+            .map(|(bucket, words)| (bucket, serde_json::json!({ "verbs": words })))
+            .collect();
+        let output = File::create("frequency_list_stratified.json")?;
+        serde_json::to_writer(std::io::BufWriter::new(output), &buckets)?;
+    }
+
+    let default_output_name = match dictionary {
+        Dictionary::Ipadic => "frequency_list_ipadic.json",
+        Dictionary::UniDic => "frequency_list_unidic.json",
+    };
+    let output_name = opt.output.as_deref().unwrap_or(default_output_name);
+    let output = File::create(output_name)?;
     let writer = std::io::BufWriter::new(output);
 
     let json = serde_json::json!({
@@ -455,12 +467,15 @@ fn process_with_ipadic() -> anyhow::Result<()> {
 }
 
 fn main() -> anyhow::Result<()> {
+    let opt = Opt::parse();
+    let cli::Command::Build(build_opt) = opt.command;
+
     rayon::ThreadPoolBuilder::new()
-        .num_threads(32)
+        .num_threads(build_opt.threads)
         .build_global()
         .unwrap();
 
-    process_with_ipadic()?;
+    process(&build_opt)?;
 
     Ok(())
 }